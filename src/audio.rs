@@ -0,0 +1,64 @@
+//! Sound effects and background music
+
+use bevy::prelude::*;
+use crate::components::*;
+
+// Handles to the game's sound clips, loaded once during setup
+#[derive(Resource)]
+pub struct AudioAssets {
+    pub laser: Handle<AudioSource>,
+    pub explosion: Handle<AudioSource>,
+    pub player_hit: Handle<AudioSource>,
+    pub game_over: Handle<AudioSource>,
+    pub background: Handle<AudioSource>,
+}
+
+impl AudioAssets {
+    pub fn load(asset_server: &AssetServer) -> Self {
+        AudioAssets {
+            laser: asset_server.load("audio/laser.ogg"),
+            explosion: asset_server.load("audio/explosion.ogg"),
+            player_hit: asset_server.load("audio/player_hit.ogg"),
+            game_over: asset_server.load("audio/game_over.ogg"),
+            background: asset_server.load("audio/background.ogg"),
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct BackgroundMusic;
+
+// Stop the background track
+pub fn stop_background_music(mut commands: Commands, query: Query<Entity, With<BackgroundMusic>>) {
+    for music in &query {
+        commands.entity(music).despawn();
+    }
+}
+
+// Play the game over clip as a one-shot
+pub fn play_game_over_sfx(mut commands: Commands, audio_assets: Res<AudioAssets>) {
+    commands.spawn(AudioBundle {
+        source: audio_assets.game_over.clone(),
+        settings: PlaybackSettings::DESPAWN,
+    });
+}
+
+// React to collision events with the matching sound effect, without
+// duplicating the collision math that already lives in check_for_collisions
+pub fn play_collision_sfx(
+    mut commands: Commands,
+    audio_assets: Res<AudioAssets>,
+    mut collision_events: EventReader<CollisionEvent>,
+) {
+    for event in collision_events.iter() {
+        let clip = match event.kind {
+            CollisionKind::AlienHit => audio_assets.explosion.clone(),
+            CollisionKind::PlayerHit => audio_assets.player_hit.clone(),
+            CollisionKind::AlienEscaped => continue,
+        };
+        commands.spawn(AudioBundle {
+            source: clip,
+            settings: PlaybackSettings::DESPAWN,
+        });
+    }
+}
@@ -9,14 +9,26 @@ pub struct Spaceship;
 #[derive(Component)]
 pub struct Laser;
 
+#[derive(Component)]
+pub struct AlienLaser;
+
 #[derive(Component, Deref, DerefMut)]
 pub struct Velocity(pub Vec2);
 
 #[derive(Component)]
 pub struct Collider;
 
-#[derive(Event, Default)]
-pub struct CollisionEvent;
+#[derive(Debug, Clone, Copy)]
+pub enum CollisionKind {
+    AlienHit,
+    PlayerHit,
+    AlienEscaped,
+}
+
+#[derive(Event)]
+pub struct CollisionEvent {
+    pub kind: CollisionKind,
+}
 
 #[derive(Component)]
 pub struct Alien;
@@ -24,6 +36,42 @@ pub struct Alien;
 #[derive(Component)]
 pub struct Instructions;
 
+#[derive(Component)]
+pub struct PausedText;
+
+#[derive(Component)]
+pub struct Shield;
+
+// This bundle is a collection of the components that define a single
+// eroding block of a defense bunker
+#[derive(Bundle)]
+pub struct ShieldBundle {
+    sprite_bundle: SpriteBundle,
+    collider: Collider,
+    shield: Shield,
+}
+
+impl ShieldBundle {
+    pub fn new(position: Vec2) -> ShieldBundle {
+        ShieldBundle {
+            sprite_bundle: SpriteBundle {
+                transform: Transform {
+                    translation: position.extend(0.0),
+                    scale: SHIELD_BLOCK_SIZE.extend(1.0),
+                    ..default()
+                },
+                sprite: Sprite {
+                    color: SHIELD_COLOR,
+                    ..default()
+                },
+                ..default()
+            },
+            collider: Collider,
+            shield: Shield,
+        }
+    }
+}
+
 /// Which side of the arena is this wall located on?
 pub enum WallLocation {
     Left,
@@ -11,7 +11,6 @@ pub const LASER_SIZE: Vec3 = Vec3::new(15.0, 15.0, 0.0);
 pub const LASER_SPEED: f32 = 700.0;
 pub const ALIEN_SPEED: f32 = 300.0;
 pub const INITIAL_LASER_DIRECTION: Vec2 = Vec2::new(0., 1.);
-pub const INITIAL_ALIEN_DIRECTION: Vec2 = Vec2::new(0., -1.);
 pub const WALL_THICKNESS: f32 = 10.0;
 pub const LEFT_WALL: f32 = -450.;
 pub const RIGHT_WALL: f32 = 450.;
@@ -34,4 +33,39 @@ pub const WALL_COLOR: Color = Color::rgb(0.8, 0.8, 0.8);
 pub const TEXT_COLOR: Color = Color::rgb(0.5, 0.5, 1.0);
 pub const SCORE_COLOR: Color = Color::rgb(1.0, 0.5, 0.5);
 
-pub const ALIEN_SPAWN_TIME: f32 = 1.0; // new alien every second
\ No newline at end of file
+pub const ALIEN_SPAWN_TIME: f32 = 1.0; // new alien every second
+
+// Elliptical entrance swoop: a newly spawned alien attacks in on a curving
+// path before settling into its marching-fleet slot
+pub const FORMATION_MEMBERS_PER_WAVE: u32 = 6; // aliens sharing one swoop template before a new one is rolled
+pub const FORMATION_RADIUS_MIN: Vec2 = Vec2::new(80.0, 60.0);
+pub const FORMATION_RADIUS_MAX: Vec2 = Vec2::new(200.0, 150.0);
+pub const FORMATION_SPEED_MIN: f32 = 1.0;
+pub const FORMATION_SPEED_MAX: f32 = 2.5;
+pub const FORMATION_ARRIVAL_EPSILON: f32 = 10.0; // slack added to each ellipse radius when checking whether an arriving alien has reached the ellipse
+
+// Defense bunkers
+pub const SHIELD_COLOR: Color = Color::rgb(0.3, 0.7, 0.3);
+pub const SHIELD_BLOCK_SIZE: Vec2 = Vec2::new(10.0, 10.0);
+pub const SHIELD_BLOCK_ROWS: i32 = 4;
+pub const SHIELD_BLOCK_COLS: i32 = 6;
+pub const NUM_SHIELDS: i32 = 4;
+pub const SHIELD_Y: f32 = BOTTOM_WALL + GAP_BETWEEN_SPACESHIP_AND_FLOOR + 120.0;
+pub const SHIELD_SPACING: f32 = 200.0; // distance between neighbouring bunkers' centers
+
+// Alien return fire
+pub const ALIEN_LASER_COLOR: Color = Color::rgb(1.0, 0.8, 0.2);
+pub const ALIEN_LASER_SPEED: f32 = 500.0;
+pub const ALIEN_LASER_DIRECTION: Vec2 = Vec2::new(0., -1.);
+pub const ALIEN_LASER_FIRE_INTERVAL_MIN: f32 = 0.5;
+pub const ALIEN_LASER_FIRE_INTERVAL_MAX: f32 = 2.0;
+
+// Marching fleet
+pub const ALIEN_STEP_DOWN: f32 = 20.0; // how far the fleet drops each time it bounces off a wall
+pub const ALIEN_FLEET_REFERENCE_COUNT: u32 = 10; // alive count at which the fleet moves at ALIEN_SPEED
+pub const ALIEN_FLEET_MAX_SPEED_MULTIPLIER: f32 = 4.0; // caps the speed-up as the fleet thins out
+pub const ALIEN_GRID_ROWS: i32 = 4;
+pub const ALIEN_GRID_COLS: i32 = 8;
+pub const ALIEN_GRID_H_SPACING: f32 = 80.0;
+pub const ALIEN_GRID_V_SPACING: f32 = 50.0;
+pub const ALIEN_GRID_TOP_Y: f32 = TOP_WALL - 80.0;
\ No newline at end of file
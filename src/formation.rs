@@ -0,0 +1,116 @@
+//! Elliptical entrance swoop for newly spawned aliens
+//!
+//! Each alien attacks in on a curving path from its spawn point, then, once
+//! it has swept one full loop of its ellipse, docks into its assigned slot
+//! in the marching fleet (see the `AlienFleet` resource in `main.rs`) and
+//! hands off to lockstep movement.
+
+use bevy::prelude::*;
+use rand::Rng;
+use crate::constants::*;
+use crate::components::*;
+
+// Tracks an alien's progress around its entrance ellipse. Removed (along
+// with its Velocity) once the alien docks into the fleet.
+//
+// `pivot` is the alien's own fleet grid slot rather than one pivot shared by
+// the whole squad: each alien must dock somewhere distinct, so the slot is
+// assigned per-alien instead of per-squad. `radius`/`speed` are still drawn
+// from one shared template per squad (see FormationMaker) so a wave still
+// reads as one coordinated attack.
+#[derive(Component)]
+pub struct Formation {
+    pub start: Vec2,
+    pub pivot: Vec2,
+    pub radius: Vec2,
+    pub speed: f32,
+    pub angle: f32,
+    pub reached_ellipse: bool,
+    pub orbited: f32,
+}
+
+struct FormationTemplate {
+    radius: Vec2,
+    speed: f32,
+}
+
+// Remembers the current squad's swoop radius/speed so a whole wave of
+// entrants shares one template before a new random template is generated
+#[derive(Resource, Default)]
+pub struct FormationMaker {
+    current_template: Option<FormationTemplate>,
+    members: u32,
+}
+
+impl FormationMaker {
+    /// Builds the entrance formation for a newly spawned alien bound for `pivot`.
+    pub fn make(&mut self, start: Vec2, pivot: Vec2) -> Formation {
+        if self.current_template.is_none() || self.members >= FORMATION_MEMBERS_PER_WAVE {
+            self.current_template = Some(Self::random_template());
+            self.members = 0;
+        }
+        self.members += 1;
+        let template = self.current_template.as_ref().unwrap();
+
+        Formation {
+            start,
+            pivot,
+            radius: template.radius,
+            speed: template.speed,
+            angle: (start.y - pivot.y).atan2(start.x - pivot.x),
+            reached_ellipse: false,
+            orbited: 0.0,
+        }
+    }
+
+    fn random_template() -> FormationTemplate {
+        let mut rng = rand::thread_rng();
+        let radius = Vec2::new(
+            rng.gen_range(FORMATION_RADIUS_MIN.x..FORMATION_RADIUS_MAX.x),
+            rng.gen_range(FORMATION_RADIUS_MIN.y..FORMATION_RADIUS_MAX.y),
+        );
+        let speed = rng.gen_range(FORMATION_SPEED_MIN..FORMATION_SPEED_MAX);
+        FormationTemplate { radius, speed }
+    }
+}
+
+// Steers each arriving alien along its formation ellipse: flies straight
+// from its spawn point toward the pivot until it first reaches the ellipse,
+// then sweeps around it. Once it has completed a full sweep, it docks into
+// its fleet slot and is handed off to the lockstep fleet movement.
+pub fn move_in_formation(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Transform, &mut Velocity, &mut Formation), With<Alien>>,
+    time: Res<Time>,
+) {
+    for (entity, mut transform, mut velocity, mut formation) in &mut query {
+        let current = transform.translation.truncate();
+
+        // Inside the ellipse (grown by FORMATION_ARRIVAL_EPSILON) counts as having reached it
+        let relative = (current - formation.pivot) / (formation.radius + Vec2::splat(FORMATION_ARRIVAL_EPSILON));
+        if !formation.reached_ellipse && relative.length() <= 1.0 {
+            formation.reached_ellipse = true;
+        }
+
+        if !formation.reached_ellipse {
+            velocity.0 = (formation.pivot - current).normalize_or_zero() * ALIEN_SPEED;
+            continue;
+        }
+
+        let delta_angle = formation.speed * time.delta_seconds();
+        formation.angle += delta_angle;
+        formation.orbited += delta_angle.abs();
+
+        if formation.orbited >= std::f32::consts::TAU {
+            // One full attack sweep: dock into the assigned fleet slot
+            transform.translation.x = formation.pivot.x;
+            transform.translation.y = formation.pivot.y;
+            commands.entity(entity).remove::<Formation>();
+            commands.entity(entity).remove::<Velocity>();
+            continue;
+        }
+
+        let target = formation.pivot + Vec2::new(formation.angle.cos(), formation.angle.sin()) * formation.radius;
+        velocity.0 = (target - current).normalize_or_zero() * ALIEN_SPEED;
+    }
+}
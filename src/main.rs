@@ -6,8 +6,12 @@ use bevy::{
     sprite::MaterialMesh2dBundle
 };
 use rand::Rng;
+use std::collections::HashSet;
+use std::time::Duration;
 use crate::constants::*;
 use crate::components::*;
+use crate::formation::*;
+use crate::audio::*;
 
 fn main() {
     App::new()
@@ -16,6 +20,9 @@ fn main() {
         .insert_resource(LivesCounter { count: 3 })
         .insert_resource(ClearColor(BACKGROUND_COLOR))
         .init_resource::<AlienSpawnTimer>()
+        .init_resource::<AlienLaserTimer>()
+        .init_resource::<AlienFleet>()
+        .init_resource::<FormationMaker>()
         .add_event::<CollisionEvent>()
         .add_state::<GameState>()
         .add_systems(OnEnter(GameState::MainMenu), setup)
@@ -27,9 +34,13 @@ fn main() {
         .add_systems(
             FixedUpdate,
             (
+                move_alien_fleet,
+                move_in_formation,
                 apply_velocity,
                 move_spaceship,
                 fire_laser,
+                tick_alien_laser_timer,
+                fire_alien_laser,
                 check_for_collisions,
                 tick_alien_spawn_timer,
                 spawn_alien,
@@ -37,8 +48,12 @@ fn main() {
                 .chain()
                 .run_if(in_state(GameState::InGame))
         )
-        .add_systems(Update, (update_scoreboard, update_lives_remaining).run_if(in_state(GameState::InGame)))
-        .add_systems(OnEnter(GameState::GameOver), display_game_over)
+        .add_systems(Update, (update_scoreboard, update_lives_remaining, play_collision_sfx).run_if(in_state(GameState::InGame)))
+        .add_systems(Update, pause_game.run_if(in_state(GameState::InGame)))
+        .add_systems(Update, resume_game.run_if(in_state(GameState::Paused)))
+        .add_systems(OnEnter(GameState::Paused), display_paused)
+        .add_systems(OnExit(GameState::Paused), despawn_paused_text)
+        .add_systems(OnEnter(GameState::GameOver), (display_game_over, stop_background_music, play_game_over_sfx))
         .add_systems(Update, bevy::window::close_on_esc) // apply to all states
         .run();
 }
@@ -48,6 +63,7 @@ enum GameState {
     #[default]
     MainMenu,
     InGame,
+    Paused,
     GameOver,
 }
 
@@ -72,19 +88,103 @@ struct AlienSpawnTimer {
 
 impl Default for AlienSpawnTimer {
     fn default() -> Self {
-        AlienSpawnTimer { 
+        AlienSpawnTimer {
             timer: Timer::from_seconds(ALIEN_SPAWN_TIME, TimerMode::Repeating),
         }
     }
 }
 
+#[derive(Resource)]
+struct AlienLaserTimer {
+    timer: Timer,
+}
+
+impl Default for AlienLaserTimer {
+    fn default() -> Self {
+        AlienLaserTimer {
+            timer: Timer::from_seconds(random_alien_laser_interval(), TimerMode::Once),
+        }
+    }
+}
+
+fn random_alien_laser_interval() -> f32 {
+    rand::thread_rng().gen_range(ALIEN_LASER_FIRE_INTERVAL_MIN..ALIEN_LASER_FIRE_INTERVAL_MAX)
+}
+
+// Tracks the lockstep alien fleet: the shared direction/speed every docked
+// alien marches with, how far the whole grid has drifted since the game
+// started (so newly spawned aliens can be slotted in at the right spot),
+// and how many aliens are alive so speed can scale as they thin out
+#[derive(Resource)]
+struct AlienFleet {
+    direction: f32,
+    speed: f32,
+    alive_count: u32,
+    offset: Vec2,
+    next_slot: u32,
+}
+
+impl Default for AlienFleet {
+    fn default() -> Self {
+        AlienFleet {
+            direction: 1.0,
+            speed: ALIEN_SPEED,
+            alive_count: 0,
+            offset: Vec2::ZERO,
+            next_slot: 0,
+        }
+    }
+}
+
+impl AlienFleet {
+    fn record_spawn(&mut self) {
+        self.alive_count += 1;
+        self.rescale_speed();
+    }
+
+    fn record_despawn(&mut self) {
+        self.alive_count = self.alive_count.saturating_sub(1);
+        self.rescale_speed();
+    }
+
+    fn rescale_speed(&mut self) {
+        let multiplier = (ALIEN_FLEET_REFERENCE_COUNT as f32 / self.alive_count.max(1) as f32)
+            .min(ALIEN_FLEET_MAX_SPEED_MULTIPLIER);
+        self.speed = ALIEN_SPEED * multiplier;
+    }
+
+    /// Returns the next grid slot for a newly spawned alien to dock into,
+    /// already shifted by how far the fleet has drifted so far.
+    fn next_grid_slot(&mut self) -> Vec2 {
+        let slot = self.next_slot;
+        self.next_slot = (slot + 1) % (ALIEN_GRID_ROWS * ALIEN_GRID_COLS) as u32;
+        let row = (slot as i32) / ALIEN_GRID_COLS;
+        let col = (slot as i32) % ALIEN_GRID_COLS;
+        grid_slot_position(row, col) + self.offset
+    }
+}
+
+// The resting position of a fleet grid cell, before the fleet's drift offset is applied
+fn grid_slot_position(row: i32, col: i32) -> Vec2 {
+    let grid_width = (ALIEN_GRID_COLS - 1) as f32 * ALIEN_GRID_H_SPACING;
+    let first_x = -grid_width / 2.0;
+    Vec2::new(
+        first_x + col as f32 * ALIEN_GRID_H_SPACING,
+        ALIEN_GRID_TOP_Y - row as f32 * ALIEN_GRID_V_SPACING,
+    )
+}
+
 // Add the game's entities
 fn setup(
     mut commands: Commands,
+    asset_server: Res<AssetServer>,
 ) {
     // Camera
     commands.spawn(Camera2dBundle::default());
 
+    // Audio clips
+    commands.insert_resource(AudioAssets::load(&asset_server));
+
     // Spaceship
     let spaceship_y = BOTTOM_WALL + GAP_BETWEEN_SPACESHIP_AND_FLOOR;
     commands.spawn((
@@ -148,6 +248,9 @@ fn setup(
     commands.spawn(WallBundle::new(WallLocation::Bottom));
     commands.spawn(WallBundle::new(WallLocation::Top));
 
+    // Defense bunkers
+    spawn_shields(&mut commands);
+
     // Instructions
     commands.spawn((
         TextBundle::from_sections([
@@ -175,6 +278,27 @@ fn setup(
     ));
 }
 
+// Spawn a row of bunkers, each a grid of small blocks so they can erode piecewise
+fn spawn_shields(commands: &mut Commands) {
+    let first_shield_x = -SHIELD_SPACING * (NUM_SHIELDS - 1) as f32 / 2.0;
+    for shield_index in 0..NUM_SHIELDS {
+        let shield_x = first_shield_x + shield_index as f32 * SHIELD_SPACING;
+        let bunker_width = SHIELD_BLOCK_COLS as f32 * SHIELD_BLOCK_SIZE.x;
+        let bunker_height = SHIELD_BLOCK_ROWS as f32 * SHIELD_BLOCK_SIZE.y;
+        let first_block_x = shield_x - bunker_width / 2.0 + SHIELD_BLOCK_SIZE.x / 2.0;
+        let first_block_y = SHIELD_Y - bunker_height / 2.0 + SHIELD_BLOCK_SIZE.y / 2.0;
+        for row in 0..SHIELD_BLOCK_ROWS {
+            for col in 0..SHIELD_BLOCK_COLS {
+                let block_position = Vec2::new(
+                    first_block_x + col as f32 * SHIELD_BLOCK_SIZE.x,
+                    first_block_y + row as f32 * SHIELD_BLOCK_SIZE.y,
+                );
+                commands.spawn(ShieldBundle::new(block_position));
+            }
+        }
+    }
+}
+
 // Use keyboard input to move the spaceship
 fn move_spaceship(
     keyboard_input: Res<Input<KeyCode>>,
@@ -210,20 +334,75 @@ fn start_game(
     keyboard_input: Res<Input<KeyCode>>,
     mut next_state: ResMut<NextState<GameState>>,
     mut query: Query<Entity, With<Instructions>>,
+    audio_assets: Res<AudioAssets>,
 ) {
     if keyboard_input.just_pressed(KeyCode::Return) {
         let instructions = query.single_mut();
         commands.entity(instructions).despawn();
         next_state.set(GameState::InGame);
+        commands.spawn((
+            AudioBundle {
+                source: audio_assets.background.clone(),
+                settings: PlaybackSettings::LOOP,
+            },
+            BackgroundMusic,
+        ));
+    }
+}
+
+// Pause the game, stopping the FixedUpdate gameplay chain from running
+fn pause_game(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::P) {
+        next_state.set(GameState::Paused);
+    }
+}
+
+// Resume the game from a paused state
+fn resume_game(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::P) || keyboard_input.just_pressed(KeyCode::S) {
+        next_state.set(GameState::InGame);
     }
 }
 
+// Spawn a centered "PAUSED" overlay
+fn display_paused(mut commands: Commands) {
+    commands.spawn((
+        TextBundle::from_section(
+            "PAUSED",
+            TextStyle {
+                font_size: GAME_OVER_FONT_SIZE,
+                color: TEXT_COLOR,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            align_self: AlignSelf::Center,
+            justify_self: JustifySelf::Center,
+            ..default()
+        }),
+        PausedText,
+    ));
+}
+
+// Despawn the "PAUSED" overlay when resuming
+fn despawn_paused_text(mut commands: Commands, query: Query<Entity, With<PausedText>>) {
+    let paused_text = query.single();
+    commands.entity(paused_text).despawn();
+}
+
 fn fire_laser(
     mut commands: Commands,
     keyboard_input: Res<Input<KeyCode>>,
     mut query: Query<&mut Transform, With<Spaceship>>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
+    audio_assets: Res<AudioAssets>,
 ) {
     let mut spaceship_transform = query.single_mut().translation;
     spaceship_transform.y = spaceship_transform.y + SPACESHIP_SIZE.y;
@@ -238,9 +417,106 @@ fn fire_laser(
             Laser,
             Velocity(INITIAL_LASER_DIRECTION.normalize() * LASER_SPEED),
         ));
+        commands.spawn(AudioBundle {
+            source: audio_assets.laser.clone(),
+            settings: PlaybackSettings::DESPAWN,
+        });
     }
 }
 
+// Increment timer for the next alien laser, on a repeating randomized interval
+fn tick_alien_laser_timer(
+    mut alien_laser_timer: ResMut<AlienLaserTimer>,
+    time: Res<Time>,
+) {
+    alien_laser_timer.timer.tick(time.delta());
+}
+
+// Mirrors fire_laser, but a random live alien fires downward instead of the player
+fn fire_alien_laser(
+    mut commands: Commands,
+    mut alien_laser_timer: ResMut<AlienLaserTimer>,
+    alien_query: Query<&Transform, With<Alien>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    if !alien_laser_timer.timer.finished() {
+        return;
+    }
+    // Re-roll the interval so successive alien shots aren't evenly spaced
+    alien_laser_timer.timer.set_duration(Duration::from_secs_f32(random_alien_laser_interval()));
+    alien_laser_timer.timer.reset();
+
+    let aliens: Vec<&Transform> = alien_query.iter().collect();
+    if aliens.is_empty() {
+        return;
+    }
+    let shooter = aliens[rand::thread_rng().gen_range(0..aliens.len())];
+    let mut spawn_position = shooter.translation;
+    spawn_position.y -= ALIEN_SIZE.y;
+
+    commands.spawn((
+        MaterialMesh2dBundle {
+            mesh: meshes.add(shape::Circle::default().into()).into(),
+            material: materials.add(ColorMaterial::from(ALIEN_LASER_COLOR)),
+            transform: Transform::from_translation(spawn_position).with_scale(LASER_SIZE),
+            ..default()
+        },
+        AlienLaser,
+        Velocity(ALIEN_LASER_DIRECTION.normalize() * ALIEN_LASER_SPEED),
+    ));
+}
+
+// Marches the docked alien fleet in lockstep: every alien that has finished
+// its entrance swoop (i.e. no longer has a Formation) moves together
+// horizontally, and the fleet reverses and steps down when it touches a wall.
+// Aliens still swooping in are left to move_in_formation, but their dock
+// pivot is nudged by the same delta so it doesn't go stale while they swoop.
+fn move_alien_fleet(
+    mut alien_fleet: ResMut<AlienFleet>,
+    mut query: Query<&mut Transform, (With<Alien>, Without<Formation>)>,
+    mut swooping_query: Query<&mut Formation>,
+    time: Res<Time>,
+) {
+    let mut min_x = f32::MAX;
+    let mut max_x = f32::MIN;
+    for transform in &query {
+        min_x = min_x.min(transform.translation.x);
+        max_x = max_x.max(transform.translation.x);
+    }
+    if min_x > max_x {
+        return;
+    }
+
+    let touching_left = min_x - ALIEN_SIZE.x / 2.0 <= LEFT_WALL;
+    let touching_right = max_x + ALIEN_SIZE.x / 2.0 >= RIGHT_WALL;
+    let mut step_down = 0.0;
+    if touching_left && alien_fleet.direction < 0.0 {
+        alien_fleet.direction = 1.0;
+        step_down = ALIEN_STEP_DOWN;
+    } else if touching_right && alien_fleet.direction > 0.0 {
+        alien_fleet.direction = -1.0;
+        step_down = ALIEN_STEP_DOWN;
+    }
+
+    let dx = alien_fleet.direction * alien_fleet.speed * time.delta_seconds();
+    for mut transform in &mut query {
+        transform.translation.x += dx;
+        transform.translation.y -= step_down;
+    }
+
+    // Keep swooping aliens' dock targets in lockstep with the fleet too,
+    // so they don't dock behind where the fleet has since moved to
+    for mut formation in &mut swooping_query {
+        formation.pivot.x += dx;
+        formation.pivot.y -= step_down;
+    }
+
+    // Remember the drift so newly spawned aliens dock at the fleet's current position
+    alien_fleet.offset.x += dx;
+    alien_fleet.offset.y -= step_down;
+}
+
 // Apply velocity to any entity with the Velocity component
 fn apply_velocity(mut query: Query<(&mut Transform, &Velocity)>, time: Res<Time>) {
     for (mut transform, velocity) in &mut query {
@@ -272,17 +548,22 @@ fn tick_alien_spawn_timer(
     alien_spawn_timer.timer.tick(time.delta());
 }
 
-// Spawn an alien from a random starting position
+// Spawn an alien from a random starting position, bound for the next slot
+// in turn of the marching fleet's grid (see AlienFleet::next_grid_slot);
+// slots wrap around and are not checked for a live alien already in them
 fn spawn_alien(
     mut commands: Commands,
-    alien_spawn_timer: Res<AlienSpawnTimer>
+    alien_spawn_timer: Res<AlienSpawnTimer>,
+    mut alien_fleet: ResMut<AlienFleet>,
+    mut formation_maker: ResMut<FormationMaker>,
 ) {
     if alien_spawn_timer.timer.finished() {
         let lower_bound = LEFT_WALL + ALIEN_SIZE.x;
         let upper_bound = RIGHT_WALL - ALIEN_SIZE.x;
         let starting_x = rand::thread_rng().gen_range(lower_bound..upper_bound);
         let starting_y = TOP_WALL - ALIEN_SIZE.x / 2.0;
-        let alien_position = Vec2::new(starting_x, starting_y);
+        let start = Vec2::new(starting_x, starting_y);
+        let slot = alien_fleet.next_grid_slot();
         commands.spawn((
             SpriteBundle {
                 sprite: Sprite {
@@ -290,7 +571,7 @@ fn spawn_alien(
                     ..default()
                 },
                 transform: Transform {
-                    translation: alien_position.extend(0.0),
+                    translation: start.extend(0.0),
                     scale: Vec3::new(ALIEN_SIZE.x, ALIEN_SIZE.y, 1.0),
                     ..default()
                 },
@@ -298,10 +579,12 @@ fn spawn_alien(
             },
             Alien,
             Collider,
-            Velocity(INITIAL_ALIEN_DIRECTION.normalize() * ALIEN_SPEED),
+            Velocity(Vec2::ZERO),
+            formation_maker.make(start, slot),
         ));
+        alien_fleet.record_spawn();
     }
-    
+
 }
 
 fn check_for_collisions(
@@ -309,13 +592,25 @@ fn check_for_collisions(
     mut scoreboard: ResMut<Scoreboard>,
     mut lives_remaining: ResMut<LivesCounter>,
     laser_query: Query<(Entity, &Transform), With<Laser>>,
-    collider_query: Query<(Entity, &Transform, Option<&Alien>), With<Collider>>,
+    alien_laser_query: Query<(Entity, &Transform), With<AlienLaser>>,
+    shield_query: Query<(Entity, &Transform), With<Shield>>,
+    collider_query: Query<(Entity, &Transform, Option<&Alien>, Option<&Shield>), With<Collider>>,
     spaceship_query: Query<&Transform, With<Spaceship>>,
     mut collision_events: EventWriter<CollisionEvent>,
+    mut alien_fleet: ResMut<AlienFleet>,
 ) {
-    for (collider_entity, transform, maybe_alien) in &collider_query {
+    // A laser's despawn command is deferred until the system ends, so without
+    // this it can still match (and be despawned again) against a later
+    // collider in this same pass. Track which lasers are already spoken for
+    // so each one is only ever consumed once.
+    let mut consumed_lasers: HashSet<Entity> = HashSet::new();
+
+    for (collider_entity, transform, maybe_alien, maybe_shield) in &collider_query {
         // Check if collision was with a laser
         for (laser, laser_transform) in laser_query.iter() {
+            if consumed_lasers.contains(&laser) {
+                continue;
+            }
             let laser_size = laser_transform.scale.truncate();
             let collision = collide(
                 laser_transform.translation,
@@ -324,14 +619,22 @@ fn check_for_collisions(
                 transform.scale.truncate(),
             );
             if collision.is_some() {
-                // Sends a collision event so that other systems can react to the collision
-                collision_events.send_default();
-    
                 // Aliens should be despawned and increment the scoreboard on collision
                 if maybe_alien.is_some() {
+                    // Sends a collision event so that other systems can react to the collision
+                    collision_events.send(CollisionEvent { kind: CollisionKind::AlienHit });
                     scoreboard.score += 3;
                     commands.entity(collider_entity).despawn();
                     commands.entity(laser).despawn();
+                    consumed_lasers.insert(laser);
+                    alien_fleet.record_despawn();
+                }
+
+                // A laser chips a single shield block rather than the whole bunker
+                if maybe_shield.is_some() {
+                    commands.entity(collider_entity).despawn();
+                    commands.entity(laser).despawn();
+                    consumed_lasers.insert(laser);
                 }
             }
         }
@@ -346,8 +649,10 @@ fn check_for_collisions(
             transform.scale.truncate(),
         );
         if spaceship_collision.is_some() && maybe_alien.is_some() {
-            lives_remaining.count -= 1;
+            collision_events.send(CollisionEvent { kind: CollisionKind::PlayerHit });
+            lives_remaining.count = lives_remaining.count.saturating_sub(1);
             commands.entity(collider_entity).despawn();
+            alien_fleet.record_despawn();
         }
 
         // Check if collision was with bottom wall
@@ -358,8 +663,83 @@ fn check_for_collisions(
             transform.scale.truncate(),
         );
         if bottom_wall_collision.is_some() && maybe_alien.is_some() {
+            collision_events.send(CollisionEvent { kind: CollisionKind::AlienEscaped });
             scoreboard.score -= 1;
             commands.entity(collider_entity).despawn();
+            alien_fleet.record_despawn();
+        }
+    }
+
+    // Alien lasers duel with the player: they can hit the spaceship, be shot
+    // down by a player laser, or harmlessly cross the bottom wall
+    let spaceship_transform = spaceship_query.single();
+    for (alien_laser, alien_laser_transform) in &alien_laser_query {
+        let alien_laser_size = alien_laser_transform.scale.truncate();
+
+        let spaceship_collision = collide(
+            alien_laser_transform.translation,
+            alien_laser_size,
+            spaceship_transform.translation,
+            spaceship_transform.scale.truncate(),
+        );
+        if spaceship_collision.is_some() {
+            collision_events.send(CollisionEvent { kind: CollisionKind::PlayerHit });
+            lives_remaining.count = lives_remaining.count.saturating_sub(1);
+            commands.entity(alien_laser).despawn();
+            continue;
+        }
+
+        // An alien laser chips a single shield block rather than the whole bunker
+        let mut hit_shield = false;
+        for (shield_block, shield_transform) in &shield_query {
+            let shield_collision = collide(
+                alien_laser_transform.translation,
+                alien_laser_size,
+                shield_transform.translation,
+                shield_transform.scale.truncate(),
+            );
+            if shield_collision.is_some() {
+                commands.entity(shield_block).despawn();
+                hit_shield = true;
+                break;
+            }
+        }
+        if hit_shield {
+            commands.entity(alien_laser).despawn();
+            continue;
+        }
+
+        let mut shot_down = false;
+        for (laser, laser_transform) in &laser_query {
+            if consumed_lasers.contains(&laser) {
+                continue;
+            }
+            let laser_collision = collide(
+                alien_laser_transform.translation,
+                alien_laser_size,
+                laser_transform.translation,
+                laser_transform.scale.truncate(),
+            );
+            if laser_collision.is_some() {
+                commands.entity(laser).despawn();
+                consumed_lasers.insert(laser);
+                shot_down = true;
+                break;
+            }
+        }
+        if shot_down {
+            commands.entity(alien_laser).despawn();
+            continue;
+        }
+
+        let bottom_wall_collision = collide(
+            WallLocation::Bottom.position_3d(),
+            WallLocation::Bottom.size(),
+            alien_laser_transform.translation,
+            alien_laser_size,
+        );
+        if bottom_wall_collision.is_some() {
+            commands.entity(alien_laser).despawn();
         }
     }
 }
@@ -412,4 +792,6 @@ fn display_game_over(
 }
 
 mod components;
-mod constants;
\ No newline at end of file
+mod constants;
+mod formation;
+mod audio;
\ No newline at end of file